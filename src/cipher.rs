@@ -84,16 +84,29 @@ pub fn decrypt_block<W: Word>(
 pub fn expand_key<W: Word>(key: &[u8], rounds: usize) -> Result<Vec<W>, Error> {
   // limit described in the paper.
   const MAX_ROUNDS: usize = 256;
+
+  if rounds > MAX_ROUNDS {
+    return Err(Error::InvalidRoundsCount);
+  }
+
+  // RC5 uses t = 2 * (r + 1) subkeys.
+  schedule::<W>(key, 2 * (rounds + 1))
+}
+
+/// Runs the RC5 key schedule for a requested number of subkeys `t`.
+///
+/// Both RC5 (`t = 2r + 2`) and its RC6 sibling (`t = 2r + 4`) share this
+/// routine verbatim; only the size of the `S` array differs between them.
+pub(crate) fn schedule<W: Word>(
+  key: &[u8],
+  subkey_count: usize,
+) -> Result<Vec<W>, Error> {
   const MAX_KEY_SIZE: usize = 256;
 
   if key.len() > MAX_KEY_SIZE {
     return Err(Error::InvalidKeySize);
   }
 
-  if rounds > MAX_ROUNDS {
-    return Err(Error::InvalidRoundsCount);
-  }
-
   // 1. key bytes to words:
   let mut words: Vec<W> = key_to_words(key);
 
@@ -101,7 +114,7 @@ pub fn expand_key<W: Word>(key: &[u8], rounds: usize) -> Result<Vec<W>, Error> {
   // S[0] = Pw;
   // for i = 1 to t − 1 do
   //  S[i] = S[i − 1] + Qw;
-  let mut subkeys: Vec<W> = initialize_subkeys(rounds);
+  let mut subkeys: Vec<W> = initialize_subkeys(subkey_count);
 
   // the main key scheduling loop
   // i = j = 0
@@ -162,13 +175,13 @@ fn key_to_words<W: Word>(key: &[u8]) -> Vec<W> {
 /// S[0] = Pw;
 /// for i = 1 to t − 1 do
 ///  S[i] = S[i − 1] + Qw;
-fn initialize_subkeys<W: Word>(rounds: usize) -> Vec<W> {
-  let subkey_count = 2 * (rounds + 1); // t
+fn initialize_subkeys<W: Word>(subkey_count: usize) -> Vec<W> {
   let mut subkeys = vec![W::zero(); subkey_count];
 
-  subkeys[0] = W::P;
+  subkeys[0] = W::p();
+  let q = W::q();
   for i in 1..subkey_count {
-    subkeys[i] = subkeys[i - 1].wrapping_add(&W::Q);
+    subkeys[i] = subkeys[i - 1].wrapping_add(&q);
   }
 
   subkeys
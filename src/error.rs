@@ -19,4 +19,13 @@ pub enum Error {
 
   #[error("Invalid number of rounds")]
   InvalidRoundsCount,
+
+  #[error("Invalid IV length. Must be exactly one block")]
+  InvalidIvLength,
+
+  #[error("Invalid PKCS#7 padding")]
+  InvalidPadding,
+
+  #[error("Key derivation failed")]
+  KeyDerivation,
 }
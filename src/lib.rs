@@ -3,6 +3,10 @@
 //! This algorithm is described in this paper:
 //! https://www.grc.com/r&d/rc5.pdf
 
+// The `% block_size != 0` guards and `map(..).flatten()` chains are the house
+// idiom across the cipher; keep them rather than churning to newer lints.
+#![allow(clippy::manual_is_multiple_of, clippy::map_flatten)]
+
 use {
   crate::cipher::{encrypt_block, expand_key},
   cipher::decrypt_block,
@@ -14,6 +18,10 @@ use {
 
 pub mod cipher;
 pub mod error;
+pub mod mode;
+pub mod rc6;
+#[cfg(feature = "cipher")]
+pub mod rustcrypto;
 pub mod word;
 
 /// RC5 Context
@@ -94,6 +102,116 @@ impl<W: Word> Context<W> {
 
     Ok(plaintext)
   }
+
+  /// Encrypts `plaintext` of any length by first applying PKCS#7 padding.
+  ///
+  /// `n` bytes, each equal to `n`, are appended so that the total length
+  /// reaches the next block boundary, where `n` is in `1..=block_size`. When
+  /// the input is already block-aligned a whole extra block of padding is
+  /// added, so that [`decrypt_padded`](Context::decrypt_padded) can always
+  /// recover the original length unambiguously.
+  ///
+  /// ```
+  /// use rc5::Context;
+  /// let ctx = Context::<u32>::new(vec![0u8; 16], 12).unwrap();
+  /// let pt = *b"odd length"; // 10 bytes, not a block multiple
+  /// let ct = ctx.encrypt_padded(&pt).unwrap();
+  /// assert_eq!(ctx.decrypt_padded(&ct).unwrap(), pt);
+  /// ```
+  pub fn encrypt_padded(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+    let block_size = 2 * size_of::<W>();
+    let pad = block_size - (plaintext.len() % block_size);
+
+    let mut padded = Vec::with_capacity(plaintext.len() + pad);
+    padded.extend_from_slice(plaintext);
+    padded.resize(plaintext.len() + pad, pad as u8);
+
+    self.encrypt(&padded)
+  }
+
+  /// Decrypts ciphertext produced by [`encrypt_padded`](Context::encrypt_padded)
+  /// and strips the PKCS#7 padding, validating the trailer.
+  ///
+  /// Returns [`Error::InvalidPadding`] if the final byte is zero, exceeds the
+  /// block size, or is not matched by an equal run of trailing bytes.
+  pub fn decrypt_padded(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+    let block_size = 2 * size_of::<W>();
+    let mut plaintext = self.decrypt(ciphertext)?;
+
+    let pad = *plaintext.last().ok_or(Error::InvalidPadding)? as usize;
+    if pad == 0 || pad > block_size || pad > plaintext.len() {
+      return Err(Error::InvalidPadding);
+    }
+    if plaintext[plaintext.len() - pad..].iter().any(|&b| b as usize != pad) {
+      return Err(Error::InvalidPadding);
+    }
+
+    plaintext.truncate(plaintext.len() - pad);
+    Ok(plaintext)
+  }
+}
+
+/// Password-based construction. Gated behind the `kdf` feature.
+///
+/// These constructors turn a human password into cipher key material using a
+/// salt and a configurable work factor, then feed the derived bytes into the
+/// same key expansion as [`new`](Context::new). The intermediate derived key
+/// is zeroized after expansion, exactly as `new` zeroizes a raw key.
+#[cfg(feature = "kdf")]
+impl<W: Word> Context<W> {
+  /// Derives `key_len` bytes from `password` with PBKDF2-HMAC-SHA256 and the
+  /// given `salt`/`iterations`, then builds a context with `rounds` rounds.
+  ///
+  /// ```
+  /// use rc5::Context;
+  /// let ctx =
+  ///   Context::<u32>::from_password_pbkdf2(b"pw", b"salt", 4096, 16, 12)
+  ///     .unwrap();
+  /// let pt = *b"secret message!!"; // 16 bytes
+  /// let ct = ctx.encrypt(&pt).unwrap();
+  /// assert_eq!(ctx.decrypt(&ct).unwrap(), pt);
+  /// ```
+  pub fn from_password_pbkdf2(
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    key_len: usize,
+    rounds: usize,
+  ) -> Result<Self, Error> {
+    let mut derived = vec![0u8; key_len];
+    pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+      password,
+      salt,
+      iterations,
+      &mut derived,
+    );
+
+    // `new` takes ownership of the derived key and zeroizes it.
+    Self::new(derived, rounds)
+  }
+
+  /// Derives `key_len` bytes from `password` with scrypt parameterized by
+  /// `(log_n, r, p)` and the given `salt`, then builds a context with `rounds`
+  /// rounds. Invalid scrypt parameters surface as [`Error::KeyDerivation`].
+  pub fn from_password_scrypt(
+    password: &[u8],
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+    key_len: usize,
+    rounds: usize,
+  ) -> Result<Self, Error> {
+    let params = scrypt::Params::new(log_n, r, p, key_len)
+      .map_err(|_| Error::KeyDerivation)?;
+
+    let mut derived = vec![0u8; key_len];
+    scrypt::scrypt(password, salt, &params, &mut derived)
+      .map_err(|_| Error::KeyDerivation)?;
+
+    // `new` takes ownership of the derived key and zeroizes it.
+    Self::new(derived, rounds)
+  }
 }
 
 /// Given a key and plaintext, returns the ciphertext using a parametrized RC5.
@@ -206,3 +324,103 @@ pub fn decrypt_default(
 ) -> Result<Vec<u8>, Error> {
   decrypt::<u32>(&key, ciphertext, 12)
 }
+
+#[cfg(test)]
+mod padding_tests {
+  use crate::{error::Error, Context};
+
+  fn context() -> Context<u32> {
+    Context::<u32>::new(vec![0u8; 16], 12).unwrap()
+  }
+
+  #[test]
+  fn round_trips_across_lengths() {
+    let ctx = context();
+    for len in 0..20 {
+      let pt: Vec<u8> = (0..len as u8).collect();
+      let ct = ctx.encrypt_padded(&pt).unwrap();
+      assert_eq!(ctx.decrypt_padded(&ct).unwrap(), pt);
+    }
+  }
+
+  #[test]
+  fn appends_full_block_when_already_aligned() {
+    let ctx = context();
+    let pt = [0u8; 16]; // exactly two blocks
+    let ct = ctx.encrypt_padded(&pt).unwrap();
+    // A whole extra block of padding is added.
+    assert_eq!(ct.len(), 24);
+    assert_eq!(ctx.decrypt_padded(&ct).unwrap(), pt);
+  }
+
+  // The padding validation runs on the *decrypted* bytes, so each malformed
+  // trailer is built as plaintext, encrypted raw, then fed to `decrypt_padded`.
+  fn reject(raw: &[u8]) -> Error {
+    let ctx = context();
+    let ct = ctx.encrypt(raw).unwrap();
+    ctx.decrypt_padded(&ct).unwrap_err()
+  }
+
+  #[test]
+  fn rejects_zero_pad_byte() {
+    let raw = [0u8; 16]; // trailing byte is 0
+    assert!(matches!(reject(&raw), Error::InvalidPadding));
+  }
+
+  #[test]
+  fn rejects_pad_byte_larger_than_block() {
+    let mut raw = [0u8; 16];
+    raw[15] = 9; // block size is 8
+    assert!(matches!(reject(&raw), Error::InvalidPadding));
+  }
+
+  #[test]
+  fn rejects_mismatched_pad_run() {
+    let mut raw = [0u8; 16];
+    raw[15] = 3;
+    raw[14] = 1; // should be 3 to be valid
+    assert!(matches!(reject(&raw), Error::InvalidPadding));
+  }
+}
+
+#[cfg(all(test, feature = "kdf"))]
+mod kdf_tests {
+  use crate::{error::Error, Context};
+  use secrecy::ExposeSecret;
+
+  #[test]
+  fn pbkdf2_is_deterministic_and_round_trips() {
+    let a =
+      Context::<u32>::from_password_pbkdf2(b"pw", b"salt", 1024, 16, 12).unwrap();
+    let b =
+      Context::<u32>::from_password_pbkdf2(b"pw", b"salt", 1024, 16, 12).unwrap();
+    // Same password/salt/iterations derive the same key material.
+    assert_eq!(
+      a.expanded_key.expose_secret(),
+      b.expanded_key.expose_secret()
+    );
+
+    let pt = *b"secret message!!";
+    let ct = a.encrypt(&pt).unwrap();
+    assert_eq!(a.decrypt(&ct).unwrap(), pt);
+  }
+
+  #[test]
+  fn scrypt_round_trips() {
+    let ctx =
+      Context::<u32>::from_password_scrypt(b"pw", b"salt", 4, 8, 1, 16, 12)
+        .unwrap();
+    let pt = *b"secret message!!";
+    let ct = ctx.encrypt(&pt).unwrap();
+    assert_eq!(ctx.decrypt(&ct).unwrap(), pt);
+  }
+
+  #[test]
+  fn invalid_scrypt_params_yield_key_derivation_error() {
+    // `Context` is not `Debug`, so match on the result rather than `unwrap_err`.
+    assert!(matches!(
+      Context::<u32>::from_password_scrypt(b"pw", b"salt", 0, 0, 0, 16, 12),
+      Err(Error::KeyDerivation)
+    ));
+  }
+}
@@ -0,0 +1,283 @@
+//! # RC6 Cipher implementation
+//!
+//! RC6 is RC5's successor and was an AES finalist. It shares RC5's
+//! parameterization (`w`/`r`/`b`) and the very same magic constants and key
+//! schedule, so it lives naturally alongside [`cipher`](crate::cipher). The
+//! differences are that it operates on four `w`-bit registers A, B, C, D
+//! instead of two, mixes in an integer multiplication as a source of
+//! diffusion, and derives `2r + 4` subkeys.
+//!
+//! This algorithm is described in the RC6 specification:
+//! https://www.grc.com/r&d/rc6.pdf
+
+use {
+  crate::{cipher::schedule, error::Error, word::Word},
+  secrecy::{ExposeSecret, SecretVec, Zeroize},
+  std::mem::size_of,
+};
+
+/// RC6 Context
+///
+/// Holds the expanded key and the number of rounds, mirroring
+/// [`Context`](crate::Context) for RC5. Use it to encrypt or decrypt multiple
+/// buffers of data with the same key.
+pub struct Context<W: Word = u32> {
+  pub expanded_key: SecretVec<W>,
+  pub rounds: usize,
+}
+
+impl<W: Word> Context<W> {
+  pub fn new(mut key: Vec<u8>, rounds: usize) -> Result<Self, Error> {
+    let expanded_key = expand_key::<W>(&key, rounds)?;
+    key.zeroize();
+
+    Ok(Self {
+      expanded_key: SecretVec::new(expanded_key),
+      rounds,
+    })
+  }
+
+  /// Encrypts bytes using the RC6 context and returns the ciphertext.
+  /// The plaintext must be a multiple of the block size
+  /// (`4 * size_of::<W>()`). Padding is not implemented.
+  ///
+  /// Usage example (the RC6-32/20/16 zero-key test vector):
+  ///
+  /// ```
+  /// use rc5::rc6::Context;
+  /// let ctx = Context::<u32>::new(vec![0u8; 16], 20).unwrap();
+  /// let ct = ctx.encrypt(&[0u8; 16]).unwrap();
+  /// assert_eq!(
+  ///   ct,
+  ///   vec![
+  ///     0x8F, 0xC3, 0xA5, 0x36, 0x56, 0xB1, 0xF7, 0x78, 0xC1, 0x29, 0xDF,
+  ///     0x4E, 0x98, 0x48, 0xA4, 0x1E,
+  ///   ]
+  /// );
+  /// ```
+  pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+    let word_bytes = size_of::<W>();
+    let block_size = 4 * word_bytes;
+
+    if plaintext.len() % block_size != 0 {
+      return Err(Error::InvalidInputLength);
+    }
+
+    let mut ciphertext = Vec::new();
+    for block in plaintext.chunks(block_size) {
+      ciphertext.extend(
+        encrypt_block::<W>(self.expanded_key.expose_secret(), read_block(block)?)?
+          .into_iter()
+          .flat_map(|w| w.to_le_bytes()),
+      );
+    }
+
+    Ok(ciphertext)
+  }
+
+  pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+    let word_bytes = size_of::<W>();
+    let block_size = 4 * word_bytes;
+
+    if ciphertext.len() % block_size != 0 {
+      return Err(Error::InvalidInputLength);
+    }
+
+    let mut plaintext = Vec::new();
+    for block in ciphertext.chunks(block_size) {
+      plaintext.extend(
+        decrypt_block::<W>(self.expanded_key.expose_secret(), read_block(block)?)?
+          .into_iter()
+          .flat_map(|w| w.to_le_bytes()),
+      );
+    }
+
+    Ok(plaintext)
+  }
+}
+
+/// Block Encryption
+///
+/// With the block held in four w-bit registers A, B, C, D and the subkey array
+/// S already computed, the RC6 encryption algorithm in pseudo-code is:
+///
+/// B = B + S[0];
+/// D = D + S[1];
+/// for i = 1 to r do
+///   t = (B * (2B + 1)) <<< lg w;
+///   u = (D * (2D + 1)) <<< lg w;
+///   A = ((A ⊕ t) <<< u) + S[2i];
+///   C = ((C ⊕ u) <<< t) + S[2i + 1];
+///   (A, B, C, D) = (B, C, D, A);
+/// end for
+/// A = A + S[2r + 2];
+/// C = C + S[2r + 3];
+pub fn encrypt_block<W: Word>(
+  expanded_key: &[W],
+  block: [W; 4],
+) -> Result<[W; 4], Error> {
+  let num_rounds = (expanded_key.len() - 4) / 2;
+  let [mut a, mut b, mut c, mut d] = block;
+
+  b = b.wrapping_add(&expanded_key[0]);
+  d = d.wrapping_add(&expanded_key[1]);
+
+  for i in 1..=num_rounds {
+    let t = quad(b).rotate_left(lg_w::<W>());
+    let u = quad(d).rotate_left(lg_w::<W>());
+
+    a = a
+      .bitxor(t)
+      .rotate_left(rotation(u)?)
+      .wrapping_add(&expanded_key[2 * i]);
+    c = c
+      .bitxor(u)
+      .rotate_left(rotation(t)?)
+      .wrapping_add(&expanded_key[2 * i + 1]);
+
+    let (na, nb, nc, nd) = (b, c, d, a);
+    a = na;
+    b = nb;
+    c = nc;
+    d = nd;
+  }
+
+  a = a.wrapping_add(&expanded_key[2 * num_rounds + 2]);
+  c = c.wrapping_add(&expanded_key[2 * num_rounds + 3]);
+
+  Ok([a, b, c, d])
+}
+
+/// Block Decryption
+///
+/// The inverse of [`encrypt_block`]:
+///
+/// C = C − S[2r + 3];
+/// A = A − S[2r + 2];
+/// for i = r downto 1 do
+///   (A, B, C, D) = (D, A, B, C);
+///   u = (D * (2D + 1)) <<< lg w;
+///   t = (B * (2B + 1)) <<< lg w;
+///   C = ((C − S[2i + 1]) >>> t) ⊕ u;
+///   A = ((A − S[2i]) >>> u) ⊕ t;
+/// end for
+/// D = D − S[1];
+/// B = B − S[0];
+pub fn decrypt_block<W: Word>(
+  expanded_key: &[W],
+  block: [W; 4],
+) -> Result<[W; 4], Error> {
+  let num_rounds = (expanded_key.len() - 4) / 2;
+  let [mut a, mut b, mut c, mut d] = block;
+
+  c = c.wrapping_sub(&expanded_key[2 * num_rounds + 3]);
+  a = a.wrapping_sub(&expanded_key[2 * num_rounds + 2]);
+
+  for i in (1..=num_rounds).rev() {
+    let (na, nb, nc, nd) = (d, a, b, c);
+    a = na;
+    b = nb;
+    c = nc;
+    d = nd;
+
+    let u = quad(d).rotate_left(lg_w::<W>());
+    let t = quad(b).rotate_left(lg_w::<W>());
+
+    c = c
+      .wrapping_sub(&expanded_key[2 * i + 1])
+      .rotate_right(rotation(t)?)
+      .bitxor(u);
+    a = a
+      .wrapping_sub(&expanded_key[2 * i])
+      .rotate_right(rotation(u)?)
+      .bitxor(t);
+  }
+
+  d = d.wrapping_sub(&expanded_key[1]);
+  b = b.wrapping_sub(&expanded_key[0]);
+
+  Ok([a, b, c, d])
+}
+
+/// Key expansion for RC6.
+///
+/// Identical to RC5's schedule but produces `2r + 4` subkeys for the four
+/// registers plus the pre/post-whitening additions.
+pub fn expand_key<W: Word>(key: &[u8], rounds: usize) -> Result<Vec<W>, Error> {
+  // limit described in the paper.
+  const MAX_ROUNDS: usize = 256;
+
+  if rounds > MAX_ROUNDS {
+    return Err(Error::InvalidRoundsCount);
+  }
+
+  schedule::<W>(key, 2 * rounds + 4)
+}
+
+/// The quadratic mixing function `x * (2x + 1)`.
+fn quad<W: Word>(x: W) -> W {
+  let two_x_plus_one = x.wrapping_add(&x).wrapping_add(&W::one());
+  x.wrapping_mul(&two_x_plus_one)
+}
+
+/// `lg w` — the base-2 logarithm of the word size in bits.
+fn lg_w<W: Word>() -> u32 {
+  W::BITS.trailing_zeros()
+}
+
+/// Reduces a word to a valid data-dependent rotation amount (`value mod w`).
+fn rotation<W: Word>(value: W) -> Result<u32, Error> {
+  let amount = value.to_u128().ok_or(Error::InvalidWordSize)? % W::BITS as u128;
+  Ok(amount as u32)
+}
+
+/// Reads one block worth of little-endian bytes into four registers.
+fn read_block<W: Word>(block: &[u8]) -> Result<[W; 4], Error> {
+  let word_bytes = size_of::<W>();
+  Ok([
+    W::from_le_bytes(&block[0..word_bytes])?,
+    W::from_le_bytes(&block[word_bytes..2 * word_bytes])?,
+    W::from_le_bytes(&block[2 * word_bytes..3 * word_bytes])?,
+    W::from_le_bytes(&block[3 * word_bytes..4 * word_bytes])?,
+  ])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::Context;
+
+  fn hex(bytes: &str) -> Vec<u8> {
+    (0..bytes.len())
+      .step_by(2)
+      .map(|i| u8::from_str_radix(&bytes[i..i + 2], 16).unwrap())
+      .collect()
+  }
+
+  // Known-answer tests from the RC6 specification (RC6-32/20/16).
+  #[test]
+  fn known_answer_vectors() {
+    let ctx = Context::<u32>::new(vec![0u8; 16], 20).unwrap();
+    assert_eq!(
+      ctx.encrypt(&[0u8; 16]).unwrap(),
+      hex("8fc3a53656b1f778c129df4e9848a41e")
+    );
+
+    let key = hex("0123456789abcdef0112233445566778");
+    let pt = hex("02132435465768798a9bacbdcedfe0f1");
+    let ctx = Context::<u32>::new(key, 20).unwrap();
+    assert_eq!(
+      ctx.encrypt(&pt).unwrap(),
+      hex("524e192f4715c6231f51f6367ea43f18")
+    );
+  }
+
+  #[test]
+  fn encrypt_decrypt_round_trip() {
+    let key = hex("0123456789abcdef0112233445566778");
+    let pt = hex("02132435465768798a9bacbdcedfe0f1");
+    let ctx = Context::<u32>::new(key, 20).unwrap();
+
+    let ct = ctx.encrypt(&pt).unwrap();
+    assert_eq!(ctx.decrypt(&ct).unwrap(), pt);
+  }
+}
@@ -1,15 +1,25 @@
 use {
   crate::error::Error,
   num::{
-    traits::{WrappingAdd, WrappingSub},
+    traits::{WrappingAdd, WrappingMul, WrappingSub},
+    BigUint,
     Num,
     NumCast,
+    One,
     PrimInt,
+    Zero,
   },
   secrecy::Zeroize,
   std::{fmt::Debug, mem::size_of, ops::BitXor},
 };
 
+/// Fractional digits of `e − 2 = 0.71828182845904523536…`.
+const E_MINUS_2: &[u8] = b"71828182845904523536028747135266249775724709369996";
+
+/// Fractional digits of `φ − 1 = 0.61803398874989484820…`.
+const PHI_MINUS_1: &[u8] =
+  b"61803398874989484820458683436563811772030917980576";
+
 /// RC5 Word
 ///
 /// This is one of the parameters of the RC5 algorithm. It is the size
@@ -29,30 +39,64 @@ use {
 ///   - wrapping_sub
 ///   - rotate_left
 ///   - rotate_right
-/// 	- shr
-/// 	- shl
+///   - shr
+///   - shl
+///   - wrapping_mul
 ///
 /// Luckily all those methods are implemented for all Rust primitive types by
 /// the num crate, so you can just use those.
 ///
-/// Also the type must calculate it's P and Q magic numbers according to
-/// this formula:
+/// The P and Q magic numbers need not be supplied by implementors: the default
+/// [`Word::p`] and [`Word::q`] derive them generically from the word size `w`
+/// according to
 ///
 /// P = Odd((e − 2)2^w)
 /// Q = Odd((φ − 1)2^w)
 ///
 /// where e is the base of natural logarithms and φ is the golden ratio and w is
-/// the word size.
+/// the word size. This means any word width works, including exotic custom
+/// types, without a constant table of its own. The built-in word types
+/// (`u8` through `u128`) override both methods with pinned constants, which
+/// the generator reproduces.
 pub trait Word:
-  Num + BitXor + WrappingAdd + WrappingSub + PrimInt + NumCast + Debug + Zeroize
+  Num
+  + BitXor
+  + WrappingAdd
+  + WrappingSub
+  + WrappingMul
+  + PrimInt
+  + NumCast
+  + Debug
+  + Zeroize
 where
   Self: Sized,
 {
   /// This is the W parameter of the RC5 algorithm.
   const BITS: usize = size_of::<Self>() * 8;
 
-  const P: Self; // Odd((e − 2)2^w)
-  const Q: Self; // Odd((φ − 1)2^w)
+  /// The magic constant `P_w = Odd((e − 2)·2^w)`, derived for this word width.
+  ///
+  /// ```
+  /// use rc5::word::Word;
+  /// assert_eq!(<u16 as Word>::p(), 0xB7E1);
+  /// assert_eq!(<u32 as Word>::p(), 0xB7E15163);
+  /// assert_eq!(<u8 as Word>::p(), 0xB7);
+  /// ```
+  fn p() -> Self {
+    magic_constant::<Self>(E_MINUS_2)
+  }
+
+  /// The magic constant `Q_w = Odd((φ − 1)·2^w)`, derived for this word width.
+  ///
+  /// ```
+  /// use rc5::word::Word;
+  /// assert_eq!(<u16 as Word>::q(), 0x9E37);
+  /// assert_eq!(<u32 as Word>::q(), 0x9E3779B9);
+  /// assert_eq!(<u8 as Word>::q(), 0x9F);
+  /// ```
+  fn q() -> Self {
+    magic_constant::<Self>(PHI_MINUS_1)
+  }
 
   /// Converts a little endian byte slice to a word
   fn from_le_bytes(bytes: &[u8]) -> Result<Self, Error>;
@@ -61,11 +105,55 @@ where
   fn to_le_bytes(&self) -> Vec<u8>;
 }
 
+/// Derives a `w`-bit RC5 magic constant from the fractional decimal digits of
+/// an irrational (`e − 2` or `φ − 1`).
+///
+/// `Odd(x)` is the odd integer nearest `x`; when the rounded value is even we
+/// move to the adjacent odd integer toward `x`. All arithmetic is exact
+/// big-integer arithmetic on the supplied fractional digits, truncated to the
+/// low `w` bits, so the result is correct for any word width.
+fn magic_constant<W: Word>(frac_digits: &[u8]) -> W {
+  let numerator =
+    BigUint::parse_bytes(frac_digits, 10).expect("valid decimal digits");
+  let denominator = BigUint::from(10u8).pow(frac_digits.len() as u32);
+  let one = BigUint::one();
+
+  // scaled = (frac · 2^w); split into integer quotient and remainder.
+  let scaled = numerator << W::BITS;
+  let quotient = &scaled / &denominator;
+  let remainder = &scaled % &denominator;
+
+  // Round to nearest: fractional part is `remainder / denominator`, so we
+  // round up iff `2 · remainder >= denominator`.
+  let round_up = (&remainder << 1usize) >= denominator;
+  let mut nearest = if round_up { &quotient + &one } else { quotient };
+
+  // Odd(x): if the nearest integer is even, step to the adjacent odd integer
+  // toward x (down when we rounded up, up when we rounded down).
+  if (&nearest & &one).is_zero() {
+    nearest = if round_up { nearest - &one } else { nearest + &one };
+  }
+
+  // Truncate to w bits and reassemble the word from its little-endian bytes.
+  let mask = (&one << W::BITS) - &one;
+  let mut bytes = (nearest & mask).to_bytes_le();
+  bytes.resize(size_of::<W>(), 0);
+  W::from_le_bytes(&bytes).expect("w-bit magic constant")
+}
+
 macro_rules! impl_word {
-  ($typ:tt, $q:expr, $p:expr) => {
+  ($typ:tt, $p:expr, $q:expr) => {
     impl Word for $typ {
-      const P: $typ = $p;
-      const Q: $typ = $q;
+      // The built-in word widths pin P and Q to their verified constants so
+      // that key setup stays off the big-integer path; custom `Word` types
+      // fall back to the generic [`magic_constant`] generator.
+      fn p() -> Self {
+        $p
+      }
+
+      fn q() -> Self {
+        $q
+      }
 
       fn from_le_bytes(bytes: &[u8]) -> Result<Self, Error> {
         if bytes.len() != size_of::<Self>() {
@@ -84,11 +172,34 @@ macro_rules! impl_word {
   };
 }
 
-impl_word!(u16, 0x9E37, 0xB7E1);
-impl_word!(u32, 0x9E3779B9, 0xB7E15163);
-impl_word!(u64, 0x9E3779B97F4A7C15, 0xB7E151628AED2A6B);
+impl_word!(u8, 0xB7, 0x9F);
+impl_word!(u16, 0xB7E1, 0x9E37);
+impl_word!(u32, 0xB7E15163, 0x9E3779B9);
+impl_word!(u64, 0xB7E151628AED2A6B, 0x9E3779B97F4A7C15);
 impl_word!(
   u128,
-  0x9E3779B97F4A7C15F39CC0605CEDC835,
-  0xB7E151628AED2A6ABF7158809CF4F3C7
+  0xB7E151628AED2A6ABF7158809CF4F3C7,
+  0x9E3779B97F4A7C15F39CC0605CEDC835
 );
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // The generic generator must reproduce the pinned constants bit for bit,
+  // for every built-in word width, otherwise the fast path and the fallback
+  // would disagree for custom types.
+  #[test]
+  fn generator_reproduces_pinned_constants() {
+    assert_eq!(magic_constant::<u8>(E_MINUS_2), u8::p());
+    assert_eq!(magic_constant::<u8>(PHI_MINUS_1), u8::q());
+    assert_eq!(magic_constant::<u16>(E_MINUS_2), u16::p());
+    assert_eq!(magic_constant::<u16>(PHI_MINUS_1), u16::q());
+    assert_eq!(magic_constant::<u32>(E_MINUS_2), u32::p());
+    assert_eq!(magic_constant::<u32>(PHI_MINUS_1), u32::q());
+    assert_eq!(magic_constant::<u64>(E_MINUS_2), u64::p());
+    assert_eq!(magic_constant::<u64>(PHI_MINUS_1), u64::q());
+    assert_eq!(magic_constant::<u128>(E_MINUS_2), u128::p());
+    assert_eq!(magic_constant::<u128>(PHI_MINUS_1), u128::q());
+  }
+}